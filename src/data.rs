@@ -0,0 +1,231 @@
+//! Validates `Insert`/`Update` `data` payloads against their target table's
+//! [Column] definitions, before any SQL is produced.
+
+use crate::{Column, Constant, DataType, DefinitionFile, Statement, Table, Transaction, Type};
+use serde_json::{Map, Value};
+
+/// A JSON value converted and checked against a column's [Type].
+///
+/// Implemented in the spirit of SQL's `Type`/`FromSql` conversions: the
+/// associated fn rejects values of the wrong shape or out of bounds, and
+/// returns `None` for a `NULL` only when the column permits it.
+pub trait TryFromJson: Sized {
+    fn try_from_json(value: &Value, ty: &Type) -> Result<Self, ValueError>;
+}
+
+impl TryFromJson for Option<Constant> {
+    fn try_from_json(value: &Value, ty: &Type) -> Result<Self, ValueError> {
+        if value.is_null() {
+            return if ty.not_null {
+                Err(ValueError::NotNullable)
+            } else {
+                Ok(None)
+            };
+        }
+        constant_from_json(value, &ty.data_type).map(Some)
+    }
+}
+
+fn constant_from_json(value: &Value, data_type: &DataType) -> Result<Constant, ValueError> {
+    match data_type {
+        DataType::Bool => value
+            .as_bool()
+            .map(Constant::Bool)
+            .ok_or(ValueError::WrongType { expected: "bool" }),
+        DataType::Int { upper, lower } => {
+            let i = value.as_i64().ok_or(ValueError::WrongType { expected: "int" })?;
+            if (i as isize) < *lower || (i as isize) > *upper {
+                return Err(ValueError::OutOfBounds);
+            }
+            Ok(Constant::Int(i as usize))
+        }
+        DataType::Double { upper, lower } => {
+            let d = value.as_f64().ok_or(ValueError::WrongType { expected: "double" })?;
+            if d < *lower || d > *upper {
+                return Err(ValueError::OutOfBounds);
+            }
+            Ok(Constant::Double(d))
+        }
+        DataType::String { min_chars, max_chars } => {
+            let s = value.as_str().ok_or(ValueError::WrongType { expected: "string" })?;
+            let len = s.chars().count();
+            if len < *min_chars || len > *max_chars {
+                return Err(ValueError::OutOfBounds);
+            }
+            Ok(Constant::String(s.to_string()))
+        }
+    }
+}
+
+/// Why a single JSON value failed to validate against a [Type].
+#[derive(Debug)]
+pub enum ValueError {
+    /// The column is `not_null`, but the value was JSON `null`.
+    NotNullable,
+    /// The value's JSON shape doesn't match the column's [DataType].
+    WrongType { expected: &'static str },
+    /// The value's shape is right, but it falls outside the column's bounds
+    /// (`Int`/`Double` range, or `String` length).
+    OutOfBounds,
+}
+
+/// Why a `data` payload (an `Insert` row or the `Update` map) failed to
+/// validate against its target [Table].
+#[derive(Debug)]
+pub enum RowError {
+    /// A key in `data` doesn't name any column on the table.
+    UnknownColumn { column: String },
+    /// A `not_null` column wasn't present in an `Insert` row.
+    MissingColumn { column: String },
+    /// A present value failed [TryFromJson].
+    InvalidValue { column: String, error: ValueError },
+}
+
+/// Validates a single `data` map (an `Insert` row, or the `Update` map)
+/// against `table`'s columns.
+///
+/// `require_all_columns` should be `true` for `Insert`, where every
+/// `not_null` column must be present, and `false` for `Update`, which may
+/// touch only some columns.
+fn validate_row(data: &Map<String, Value>, table: &Table, require_all_columns: bool) -> Vec<RowError> {
+    let mut errors = Vec::new();
+
+    for key in data.keys() {
+        if !table.columns.iter().any(|c| &c.name == key) {
+            errors.push(RowError::UnknownColumn { column: key.clone() });
+        }
+    }
+
+    for column in &table.columns {
+        match data.get(&column.name) {
+            Some(value) => {
+                if let Err(error) = Option::<Constant>::try_from_json(value, &column._type) {
+                    errors.push(RowError::InvalidValue {
+                        column: column.name.clone(),
+                        error,
+                    });
+                }
+            }
+            None if require_all_columns && column._type.not_null => {
+                errors.push(RowError::MissingColumn {
+                    column: column.name.clone(),
+                });
+            }
+            None => {}
+        }
+    }
+
+    errors
+}
+
+/// A [RowError] together with where in the [DefinitionFile] it was found.
+#[derive(Debug)]
+pub struct DataValidationIssue {
+    pub transaction: usize,
+    pub statement: usize,
+    /// The index of the row within `Insert::data`; always `0` for `Update`,
+    /// which has a single `data` map.
+    pub row: usize,
+    pub error: RowError,
+}
+
+/// Walks every `Insert`/`Update` [Statement] in `file` and validates its
+/// `data` against the target table's columns, collecting every problem
+/// found rather than stopping at the first.
+pub fn validate(file: &DefinitionFile) -> Vec<DataValidationIssue> {
+    let mut issues = Vec::new();
+
+    for (transaction_idx, transaction) in file.transactions.iter().enumerate() {
+        let Some(database) = find_database(file, transaction) else {
+            continue;
+        };
+
+        for (statement_idx, statement) in transaction.statements.iter().enumerate() {
+            let (table_name, rows, require_all_columns): (_, Vec<&Map<String, Value>>, _) = match statement {
+                Statement::Insert(insert) => (&insert.table.table, insert.data.iter().collect(), true),
+                Statement::Update(update) => (&update.table.table, vec![&update.data], false),
+                _ => continue,
+            };
+
+            let Some(table) = database.tables.iter().find(|t| &t.name == table_name) else {
+                continue;
+            };
+
+            for (row_idx, row) in rows.into_iter().enumerate() {
+                for error in validate_row(row, table, require_all_columns) {
+                    issues.push(DataValidationIssue {
+                        transaction: transaction_idx,
+                        statement: statement_idx,
+                        row: row_idx,
+                        error,
+                    });
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+fn find_database<'s>(file: &'s DefinitionFile, transaction: &Transaction) -> Option<&'s crate::Database> {
+    file.databases.iter().find(|d| d.name == transaction.database.database)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn int_type(lower: isize, upper: isize) -> Type {
+        Type {
+            data_type: DataType::Int { upper, lower },
+            unique: false,
+            not_null: true,
+        }
+    }
+
+    #[test]
+    fn negative_int_within_bounds_is_accepted() {
+        let ty = int_type(-1000, 1000);
+        let value = Option::<Constant>::try_from_json(&json!(-5), &ty).expect("within bounds");
+        assert!(matches!(value, Some(Constant::Int(i)) if i == -5i64 as usize));
+    }
+
+    #[test]
+    fn negative_int_below_lower_bound_is_rejected() {
+        let ty = int_type(-10, 1000);
+        let err = Option::<Constant>::try_from_json(&json!(-11), &ty).unwrap_err();
+        assert!(matches!(err, ValueError::OutOfBounds));
+    }
+
+    #[test]
+    fn null_is_rejected_when_not_null() {
+        let ty = int_type(-10, 10);
+        let err = Option::<Constant>::try_from_json(&json!(null), &ty).unwrap_err();
+        assert!(matches!(err, ValueError::NotNullable));
+    }
+
+    #[test]
+    fn null_is_accepted_when_nullable() {
+        let mut ty = int_type(-10, 10);
+        ty.not_null = false;
+        let value = Option::<Constant>::try_from_json(&json!(null), &ty).expect("nullable column");
+        assert!(value.is_none());
+    }
+
+    #[test]
+    fn validate_row_reports_unknown_and_missing_columns() {
+        let table = Table {
+            name: "t".to_string(),
+            columns: vec![Column {
+                name: "age".to_string(),
+                _type: int_type(0, 150),
+            }],
+        };
+        let mut row = Map::new();
+        row.insert("nickname".to_string(), json!("bob"));
+        let errors = validate_row(&row, &table, true);
+        assert!(errors.iter().any(|e| matches!(e, RowError::UnknownColumn { column } if column == "nickname")));
+        assert!(errors.iter().any(|e| matches!(e, RowError::MissingColumn { column } if column == "age")));
+    }
+}
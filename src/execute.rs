@@ -0,0 +1,427 @@
+//! sqlx-backed execution engine.
+//!
+//! This is gated behind the `sqlx` feature, since it is the only part of
+//! the crate that talks to a live database rather than just shuffling the
+//! [DefinitionFile] types around.
+
+use crate::render::Dialect;
+use crate::{Constant, DataType, DefinitionFile, Expression, Statement, Transaction};
+use sqlx::any::AnyTypeInfoKind;
+use sqlx::{AnyPool, Executor, Row};
+use std::collections::HashMap;
+use std::fmt;
+
+/// A database the [DefinitionFile] can be run against: a pool to run
+/// queries on, and the [Dialect] to render SQL for it.
+pub struct Connection {
+    pub pool: AnyPool,
+    pub dialect: Box<dyn Dialect + Send + Sync>,
+}
+
+/// A schema mismatch found while checking a [DefinitionFile] against the
+/// live databases, before any [Transaction] is run.
+#[derive(Debug)]
+pub enum SchemaMismatch {
+    /// No [Connection] was provided for a [crate::Database] the file expects.
+    MissingDatabase { database: String },
+    /// A [crate::Table] the file expects does not exist.
+    MissingTable { database: String, table: String },
+    /// A [crate::Column] the file expects does not exist on its table.
+    MissingColumn {
+        database: String,
+        table: String,
+        column: String,
+    },
+    /// A [crate::Column] exists, but its live type isn't compatible with the
+    /// [DataType] the file declares.
+    IncompatibleColumn {
+        database: String,
+        table: String,
+        column: String,
+        expected: String,
+        found: String,
+    },
+}
+
+impl fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaMismatch::MissingDatabase { database } => {
+                write!(f, "no connection was provided for database `{}`", database)
+            }
+            SchemaMismatch::MissingTable { database, table } => {
+                write!(f, "table `{}` does not exist in database `{}`", table, database)
+            }
+            SchemaMismatch::MissingColumn {
+                database,
+                table,
+                column,
+            } => write!(
+                f,
+                "column `{}` does not exist on table `{}` in database `{}`",
+                column, table, database
+            ),
+            SchemaMismatch::IncompatibleColumn {
+                database,
+                table,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "column `{}` on table `{}` in database `{}` expects {} but found {}",
+                column, table, database, expected, found
+            ),
+        }
+    }
+}
+
+/// An error while executing a [DefinitionFile].
+#[derive(Debug)]
+pub enum ExecuteError {
+    /// The file's [crate::Database]/[crate::Table]/[crate::Column] layout
+    /// did not match the live databases, so, per the [DefinitionFile] docs,
+    /// ALL transactions were disregarded.
+    SchemaMismatch(Vec<SchemaMismatch>),
+    /// A [Transaction] referenced a database with no matching [Connection].
+    UnknownDatabase(String),
+    /// A [Statement]'s `execution_condition` referenced a column, so it is
+    /// evaluated against a row of the statement's own table, but that table
+    /// had no rows to evaluate it against.
+    ConditionHasNoRow { table: String },
+    /// An underlying sqlx error.
+    Sqlx(sqlx::Error),
+}
+
+impl fmt::Display for ExecuteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecuteError::SchemaMismatch(mismatches) => {
+                writeln!(f, "schema is incompatible, disregarding all transactions:")?;
+                for mismatch in mismatches {
+                    writeln!(f, "  - {}", mismatch)?;
+                }
+                Ok(())
+            }
+            ExecuteError::UnknownDatabase(database) => {
+                write!(f, "no connection was provided for database `{}`", database)
+            }
+            ExecuteError::ConditionHasNoRow { table } => write!(
+                f,
+                "execution_condition references a column of table `{}`, which has no rows",
+                table
+            ),
+            ExecuteError::Sqlx(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl std::error::Error for ExecuteError {}
+
+impl From<sqlx::Error> for ExecuteError {
+    fn from(e: sqlx::Error) -> Self {
+        ExecuteError::Sqlx(e)
+    }
+}
+
+/// Runs every [Transaction] in `file` against `connections`.
+///
+/// Before anything is executed, every [crate::Database]/[crate::Table]/
+/// [crate::Column] in `file` is checked against the live databases via
+/// sqlx's introspection (`describe`). If any mismatch is found, ALL
+/// transactions are disregarded, as required by the [DefinitionFile] docs.
+///
+/// Each [Transaction] runs inside a real `BEGIN`/`COMMIT`. If one of its
+/// [Statement]s fails, the remaining statements in that transaction are
+/// skipped and it is rolled back, per the [Transaction] docs.
+///
+/// Transactions run in order, and execution stops at the first one that
+/// fails: its error is returned and the transactions after it are not run.
+pub async fn execute(
+    file: &DefinitionFile,
+    connections: &HashMap<String, Connection>,
+) -> Result<(), ExecuteError> {
+    let mismatches = check_schema(file, connections).await?;
+    if !mismatches.is_empty() {
+        return Err(ExecuteError::SchemaMismatch(mismatches));
+    }
+
+    for transaction in &file.transactions {
+        run_transaction(transaction, connections).await?;
+    }
+
+    Ok(())
+}
+
+/// Verifies that every database/table/column in `file` exists with a
+/// compatible type, without running any transaction.
+async fn check_schema(
+    file: &DefinitionFile,
+    connections: &HashMap<String, Connection>,
+) -> Result<Vec<SchemaMismatch>, ExecuteError> {
+    let mut mismatches = Vec::new();
+
+    for database in &file.databases {
+        let Some(connection) = connections.get(&database.name) else {
+            mismatches.push(SchemaMismatch::MissingDatabase {
+                database: database.name.clone(),
+            });
+            continue;
+        };
+
+        for table in &database.tables {
+            let select_all = format!(
+                "SELECT * FROM {}",
+                connection.dialect.quote_ident(&table.name)
+            );
+            let described = connection.pool.describe(&select_all).await;
+            let described = match described {
+                Ok(d) => d,
+                // The Any driver can fail to describe a table that exists
+                // perfectly fine, e.g. it doesn't support representing
+                // SQLite's `Bool` type; that's a driver limitation, not a
+                // missing table, so don't mask it as one.
+                Err(e @ sqlx::Error::AnyDriverError(_)) => return Err(e.into()),
+                Err(_) => {
+                    mismatches.push(SchemaMismatch::MissingTable {
+                        database: database.name.clone(),
+                        table: table.name.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            for column in &table.columns {
+                let found = described
+                    .columns()
+                    .iter()
+                    .find(|c| sqlx::Column::name(*c) == column.name);
+                let Some(found) = found else {
+                    mismatches.push(SchemaMismatch::MissingColumn {
+                        database: database.name.clone(),
+                        table: table.name.clone(),
+                        column: column.name.clone(),
+                    });
+                    continue;
+                };
+                let kind = sqlx::TypeInfo::name(sqlx::Column::type_info(found)).to_string();
+                if !is_compatible(&column._type.data_type, sqlx::Column::type_info(found)) {
+                    mismatches.push(SchemaMismatch::IncompatibleColumn {
+                        database: database.name.clone(),
+                        table: table.name.clone(),
+                        column: column.name.clone(),
+                        expected: data_type_name(&column._type.data_type),
+                        found: kind,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(mismatches)
+}
+
+fn data_type_name(data_type: &DataType) -> String {
+    match data_type {
+        DataType::Bool => "a boolean".to_string(),
+        DataType::Int { .. } => "an integer".to_string(),
+        DataType::Double { .. } => "a floating-point number".to_string(),
+        DataType::String { .. } => "a string".to_string(),
+    }
+}
+
+fn is_compatible(data_type: &DataType, live: &sqlx::any::AnyTypeInfo) -> bool {
+    use AnyTypeInfoKind::*;
+    matches!(
+        (data_type, live.kind()),
+        (DataType::Bool, Bool)
+            | (DataType::Int { .. }, SmallInt | Integer | BigInt)
+            | (DataType::Double { .. }, Real | Double)
+            | (DataType::String { .. }, Text | Blob)
+    )
+}
+
+/// Runs a single [Transaction]: `BEGIN`, then every [Statement] in order,
+/// skipping the rest and rolling back as soon as one fails.
+async fn run_transaction(
+    transaction: &Transaction,
+    connections: &HashMap<String, Connection>,
+) -> Result<(), ExecuteError> {
+    let connection = connections
+        .get(&transaction.database.database)
+        .ok_or_else(|| ExecuteError::UnknownDatabase(transaction.database.database.clone()))?;
+
+    let mut tx = connection.pool.begin().await?;
+
+    for statement in &transaction.statements {
+        if !should_execute(statement, connection.dialect.as_ref(), &mut tx).await? {
+            continue;
+        }
+
+        let (sql, params) = connection.dialect.render_statement(statement);
+        if sql.is_empty() {
+            continue;
+        }
+        let query = bind_all(sqlx::query(&sql), &params);
+        if let Err(e) = query.execute(&mut *tx).await {
+            tx.rollback().await?;
+            return Err(e.into());
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Evaluates a [Statement]'s `execution_condition`, if it has one, deciding
+/// whether it should even be issued.
+async fn should_execute(
+    statement: &Statement,
+    dialect: &(dyn Dialect + Send + Sync),
+    tx: &mut sqlx::Transaction<'_, sqlx::Any>,
+) -> Result<bool, ExecuteError> {
+    let (table, condition): (&str, &Option<Expression>) = match statement {
+        Statement::NoOp(_) => return Ok(true),
+        Statement::Select(s) => (&s.table.table, &s.execution_condition),
+        Statement::Delete(d) => (&d.table.table, &d.execution_condition),
+        Statement::Insert(i) => (&i.table.table, &i.execution_condition),
+        Statement::Update(u) => (&u.table.table, &u.execution_condition),
+    };
+    let Some(condition) = condition else {
+        return Ok(true);
+    };
+
+    let mut params = Vec::new();
+    let condition_sql = dialect.render_expression(condition, &mut params);
+
+    // Most conditions are constants/arithmetic over constants and don't need
+    // a row at all; only pay for a `FROM`/`LIMIT 1` over the statement's own
+    // table when `condition` references any column at all, and treat a table
+    // with no rows to evaluate it against as a distinct error rather than
+    // silently skipping the statement.
+    if !references_any_column(condition) {
+        let select_condition = format!("SELECT {}", condition_sql);
+        let row = bind_all(sqlx::query(&select_condition), &params)
+            .fetch_one(&mut **tx)
+            .await?;
+        return Ok(row.try_get::<bool, _>(0)?);
+    }
+
+    let select_condition = format!(
+        "SELECT {} FROM {} LIMIT 1",
+        condition_sql,
+        dialect.quote_ident(table)
+    );
+    let row = bind_all(sqlx::query(&select_condition), &params)
+        .fetch_optional(&mut **tx)
+        .await?;
+    let Some(row) = row else {
+        return Err(ExecuteError::ConditionHasNoRow {
+            table: table.to_string(),
+        });
+    };
+    Ok(row.try_get::<bool, _>(0)?)
+}
+
+/// Whether `expr` contains a [crate::ColumnReference] anywhere in its tree.
+fn references_any_column(expr: &Expression) -> bool {
+    match expr {
+        Expression::Constant(_) => false,
+        Expression::ColumnReference(_) => true,
+        Expression::Not(e) => references_any_column(e),
+        Expression::NotNull { casted, default } => {
+            references_any_column(casted) || references_any_column(default)
+        }
+        Expression::Equals { left, right }
+        | Expression::LessThan { left, right }
+        | Expression::GreaterThan { left, right }
+        | Expression::LessThanOrEqual { left, right }
+        | Expression::GreaterThanOrEqual { left, right }
+        | Expression::Plus { left, right }
+        | Expression::Subtract { left, right }
+        | Expression::Divide { left, right }
+        | Expression::Multiply { left, right }
+        | Expression::Modulo { left, right }
+        | Expression::And { left, right }
+        | Expression::Or { left, right }
+        | Expression::BitAnd { left, right }
+        | Expression::BitOr { left, right }
+        | Expression::BitXOr { left, right }
+        | Expression::Contains { left, right }
+        | Expression::StartsWith { left, right }
+        | Expression::EndsWith { left, right } => {
+            references_any_column(left) || references_any_column(right)
+        }
+        Expression::Conditional {
+            condition,
+            true_path,
+            false_path,
+        } => {
+            references_any_column(condition)
+                || references_any_column(true_path)
+                || references_any_column(false_path)
+        }
+    }
+}
+
+/// Binds each [Constant] onto `query`, in order.
+fn bind_all<'q>(
+    mut query: sqlx::query::Query<'q, sqlx::Any, sqlx::any::AnyArguments<'q>>,
+    params: &'q [Constant],
+) -> sqlx::query::Query<'q, sqlx::Any, sqlx::any::AnyArguments<'q>> {
+    for param in params {
+        query = match param {
+            Constant::Bool(b) => query.bind(*b),
+            Constant::Int(i) => query.bind(*i as i64),
+            Constant::Double(d) => query.bind(*d),
+            Constant::String(s) => query.bind(s.as_str()),
+        };
+    }
+    query
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColumnReference, Constant as C, TableReference};
+
+    fn column_ref(table: &str, column: &str) -> Expression {
+        Expression::ColumnReference(ColumnReference {
+            table: TableReference { table: table.to_string() },
+            column: column.to_string(),
+        })
+    }
+
+    #[test]
+    fn references_any_column_is_false_for_pure_constants() {
+        let expr = Expression::Equals {
+            left: Box::new(Expression::Constant(C::Int(1))),
+            right: Box::new(Expression::Constant(C::Int(2))),
+        };
+        assert!(!references_any_column(&expr));
+    }
+
+    #[test]
+    fn references_any_column_is_true_when_nested_under_other_operators() {
+        let expr = Expression::Not(Box::new(Expression::Conditional {
+            condition: Box::new(Expression::Constant(C::Bool(true))),
+            true_path: Box::new(Expression::Constant(C::Int(1))),
+            false_path: Box::new(column_ref("t", "flag")),
+        }));
+        assert!(references_any_column(&expr));
+    }
+
+    #[test]
+    fn data_type_name_describes_every_variant() {
+        assert_eq!(data_type_name(&DataType::Bool), "a boolean");
+        assert_eq!(data_type_name(&DataType::Int { upper: 1, lower: 0 }), "an integer");
+        assert_eq!(
+            data_type_name(&DataType::Double { upper: 1.0, lower: 0.0 }),
+            "a floating-point number"
+        );
+        assert_eq!(
+            data_type_name(&DataType::String { min_chars: 0, max_chars: 1 }),
+            "a string"
+        );
+    }
+}
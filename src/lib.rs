@@ -1,13 +1,51 @@
 use serde::{Serialize, Deserialize};
 use serde_json::{Map, Value};
 
+pub mod render;
+#[cfg(feature = "sqlx")]
+pub mod execute;
+pub mod validate;
+pub mod data;
+pub mod v1;
+
+/// The wire format, tagged by `version` so the format can evolve without
+/// silently changing the meaning of old files.
+///
+/// Deserializing this and converting it `.into()` a [DefinitionFile] is the
+/// intended entry point for reading a file; serializing a [DefinitionFile]
+/// by first converting it `.into()` this (which always picks the latest
+/// version) is the intended entry point for writing one. The rest of the
+/// crate only ever operates on [DefinitionFile] and never branches on version.
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "version")]
+pub enum VersionedDefinitionFile {
+    V1(v1::DefinitionFile),
+}
+
+impl From<VersionedDefinitionFile> for DefinitionFile {
+    fn from(versioned: VersionedDefinitionFile) -> Self {
+        match versioned {
+            VersionedDefinitionFile::V1(file) => file.into(),
+        }
+    }
+}
+
+impl From<DefinitionFile> for VersionedDefinitionFile {
+    fn from(file: DefinitionFile) -> Self {
+        VersionedDefinitionFile::V1(file.into())
+    }
+}
+
 /// The root structure, containing the rough layout of databases,
 /// as well as the transactions to run on them.
 ///
+/// This is the canonical, version-agnostic in-memory representation; see
+/// [v1::DefinitionFile] (and any later version) for the serialized form.
+///
 /// When the [Database] is incompatible with the database system or colums are mismatched,
 /// an implementation MUST disregard ALL transactions,
 /// not just the ones that reference incompatible databases/tables.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct DefinitionFile{
     /// The databases that are expected to exist for the transactions to function
     pub databases: Vec<Database>,
@@ -18,7 +56,7 @@ pub struct DefinitionFile{
 /// A database that is expected to exist and its tables.
 ///
 /// Note that in [Transaction] the name is referenced via a [DatabaseReference]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Database{
     /// The unique identifier of the database.
     /// A database is later referenced by this name using a [DatabaseReference] in [Transaction].
@@ -35,7 +73,7 @@ pub struct Database{
 /// Note that columns are referenced by their name later on, not their ordinal.
 ///
 /// Can be referenced in an [Statement] using a [TableReference]
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Table{
     /// the unique identifier of the table.
     /// Unique in its Database (2 databases may have tables with equal names)
@@ -50,7 +88,7 @@ pub struct Table{
 
 /// A column definition with its name and type.
 /// For conversions and notes on compatibility, see [Type].
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Column{
     /// the unique identifier of the column.
     /// You can have 2 Id columns as long as they are in different tables.
@@ -61,7 +99,6 @@ pub struct Column{
     ///  - the symbols '_', '-', '.'
     pub name: String,
     /// the type of the column
-    #[serde(rename = "type", flatten)]
     pub _type: Type
 }
 
@@ -72,7 +109,7 @@ pub struct Column{
 /// on the actual database used, they cannot be set in stone here.
 ///
 /// Compatibility to your database's built in types MUST be checked, before attempting to execute [Transaction]s.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Type{
     /// the actual data type
     pub data_type: DataType,
@@ -83,8 +120,7 @@ pub struct Type{
 }
 
 /// the actual inner type of column
-#[derive(Serialize, Deserialize, Debug)]
-#[serde(tag = "type", content = "bounds")]
+#[derive(Debug)]
 pub enum DataType{
     /// A boolean, with values true and false.
     ///
@@ -110,7 +146,7 @@ pub enum DataType{
 /// not doing so should at the very least be logged.
 /// It is REQUIRED to skip statements that would occur after one that failed (and roll back the transaction, if supported).
 /// As an example with 4 statements, if statement 1 executes fine, but 2 throws an error, 3 and 4 MUST be skipped.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Transaction{
     /// the database to run statements on.
     pub database: DatabaseReference,
@@ -118,12 +154,12 @@ pub struct Transaction{
     pub statements: Vec<Statement>
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct DatabaseReference{
     pub database: String
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub enum Statement {
     NoOp(NoOp),
     Select(Select),
@@ -132,52 +168,45 @@ pub enum Statement {
     Update(Update)
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct NoOp{
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Select {
     pub table: TableReference,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub execution_condition: Option<Expression>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<Expression>
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Delete{
     pub table: TableReference,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub execution_condition: Option<Expression>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<Expression>
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Insert{
     pub table: TableReference,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub execution_condition: Option<Expression>,
     pub data: Vec<Map<String, Value>>
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct Update{
     pub table: TableReference,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub execution_condition: Option<Expression>,
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub filter: Option<Expression>,
     pub data: Map<String, Value>
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct TableReference{
     pub table: String
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub enum Expression{
     Constant(Constant),
     ColumnReference(ColumnReference),
@@ -204,7 +233,7 @@ pub enum Expression{
     Conditional{condition: Box<Expression>, true_path: Box<Expression>, false_path: Box<Expression>}
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub enum Constant{
     Bool(bool),
     Int(usize),
@@ -212,9 +241,8 @@ pub enum Constant{
     String(String)
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Debug)]
 pub struct ColumnReference {
-    #[serde(flatten)]
     pub table: TableReference,
     pub column: String
-}
\ No newline at end of file
+}
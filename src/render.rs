@@ -0,0 +1,501 @@
+//! Dialect-aware SQL generation.
+//!
+//! This module turns the in-memory [DefinitionFile](crate::DefinitionFile)
+//! representation into concrete SQL text for a chosen target database, via
+//! the [Dialect] trait and its [Postgres], [MySql] and [Sqlite]
+//! implementations.
+//!
+//! [Constant]s are never inlined into the generated SQL text. Instead they
+//! are collected into a parameter list and the SQL carries placeholders, so
+//! callers bind them through their driver of choice rather than risking the
+//! SQL-injection issue called out in [DataType::String]'s docs.
+
+use crate::{
+    Column, Constant, DataType, Delete, Expression, Insert, NoOp, Select, Statement, Table,
+    Update,
+};
+use serde_json::Value;
+
+/// Translates schema and statements from this crate's types into the SQL
+/// dialect of a specific database engine.
+pub trait Dialect {
+    /// Quotes an identifier (table or column name) for this dialect.
+    fn quote_ident(&self, ident: &str) -> String;
+
+    /// Renders the `index`th (1-based) bind placeholder, e.g. `$1` or `?`.
+    fn placeholder(&self, index: usize) -> String;
+
+    /// Maps a [DataType] to the narrowest native column type this dialect
+    /// supports for the given bounds.
+    fn data_type_ddl(&self, data_type: &DataType) -> String;
+
+    /// Renders a string concatenation of `parts`, used to glue a dynamic
+    /// `LIKE` pattern together at query time.
+    fn concat(&self, parts: &[String]) -> String;
+
+    /// The infix operator for [Expression::BitXOr]. Differs between
+    /// dialects (e.g. `#` in PostgreSQL, `^` in MySQL).
+    fn bitxor_op(&self) -> &'static str {
+        "#"
+    }
+
+    /// Renders the full `CREATE TABLE` statement for a [Table].
+    fn create_table_ddl(&self, table: &Table) -> String {
+        let columns = table
+            .columns
+            .iter()
+            .map(|c| self.column_ddl(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("CREATE TABLE {} ({})", self.quote_ident(&table.name), columns)
+    }
+
+    /// Renders the column-definition fragment used in `CREATE TABLE`, e.g.
+    /// `"age" INTEGER NOT NULL`.
+    fn column_ddl(&self, column: &Column) -> String {
+        let mut ddl = format!(
+            "{} {}",
+            self.quote_ident(&column.name),
+            self.data_type_ddl(&column._type.data_type)
+        );
+        if column._type.not_null {
+            ddl.push_str(" NOT NULL");
+        }
+        if column._type.unique {
+            ddl.push_str(" UNIQUE");
+        }
+        ddl
+    }
+
+    /// Renders a [Statement] to SQL text and the bind parameters that fill
+    /// its placeholders, in order.
+    fn render_statement(&self, statement: &Statement) -> (String, Vec<Constant>) {
+        let mut params = Vec::new();
+        let sql = match statement {
+            Statement::NoOp(n) => self.render_noop(n),
+            Statement::Select(s) => self.render_select(s, &mut params),
+            Statement::Delete(d) => self.render_delete(d, &mut params),
+            Statement::Insert(i) => self.render_insert(i, &mut params),
+            Statement::Update(u) => self.render_update(u, &mut params),
+        };
+        (sql, params)
+    }
+
+    /// Renders a [NoOp]. There is nothing to execute, so this is empty SQL.
+    fn render_noop(&self, _noop: &NoOp) -> String {
+        String::new()
+    }
+
+    fn render_select(&self, select: &Select, params: &mut Vec<Constant>) -> String {
+        let mut sql = format!("SELECT * FROM {}", self.quote_ident(&select.table.table));
+        if let Some(filter) = &select.filter {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.render_expression(filter, params));
+        }
+        sql
+    }
+
+    fn render_delete(&self, delete: &Delete, params: &mut Vec<Constant>) -> String {
+        let mut sql = format!("DELETE FROM {}", self.quote_ident(&delete.table.table));
+        if let Some(filter) = &delete.filter {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.render_expression(filter, params));
+        }
+        sql
+    }
+
+    fn render_insert(&self, insert: &Insert, params: &mut Vec<Constant>) -> String {
+        let mut rows = Vec::with_capacity(insert.data.len());
+        let mut columns: Vec<&String> = Vec::new();
+        for row in &insert.data {
+            for key in row.keys() {
+                if !columns.contains(&key) {
+                    columns.push(key);
+                }
+            }
+        }
+        for row in &insert.data {
+            let mut values = Vec::with_capacity(columns.len());
+            for column in &columns {
+                match row.get(*column) {
+                    Some(value) => values.push(self.render_json_value(value, params)),
+                    None => values.push("DEFAULT".to_string()),
+                }
+            }
+            rows.push(format!("({})", values.join(", ")));
+        }
+        let column_list = columns
+            .iter()
+            .map(|c| self.quote_ident(c))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "INSERT INTO {} ({}) VALUES {}",
+            self.quote_ident(&insert.table.table),
+            column_list,
+            rows.join(", ")
+        )
+    }
+
+    fn render_update(&self, update: &Update, params: &mut Vec<Constant>) -> String {
+        let assignments = update
+            .data
+            .iter()
+            .map(|(column, value)| {
+                format!(
+                    "{} = {}",
+                    self.quote_ident(column),
+                    self.render_json_value(value, params)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut sql = format!(
+            "UPDATE {} SET {}",
+            self.quote_ident(&update.table.table),
+            assignments
+        );
+        if let Some(filter) = &update.filter {
+            sql.push_str(" WHERE ");
+            sql.push_str(&self.render_expression(filter, params));
+        }
+        sql
+    }
+
+    /// Renders a single `data`/row value, either as `NULL` or as a bind
+    /// placeholder, pushing the matching [Constant] onto `params`.
+    fn render_json_value(&self, value: &Value, params: &mut Vec<Constant>) -> String {
+        match json_value_to_constant(value) {
+            Some(constant) => {
+                params.push(constant);
+                self.placeholder(params.len())
+            }
+            None => "NULL".to_string(),
+        }
+    }
+
+    /// Lowers an [Expression] tree into infix SQL with correct
+    /// parenthesization, pushing any [Constant]s onto `params` as bind
+    /// placeholders rather than inlining them.
+    fn render_expression(&self, expr: &Expression, params: &mut Vec<Constant>) -> String {
+        match expr {
+            Expression::Constant(c) => {
+                params.push(clone_constant(c));
+                self.placeholder(params.len())
+            }
+            Expression::ColumnReference(c) => {
+                format!("{}.{}", self.quote_ident(&c.table.table), self.quote_ident(&c.column))
+            }
+            Expression::Not(e) => format!("(NOT {})", self.render_expression(e, params)),
+            Expression::NotNull { casted, default } => format!(
+                "COALESCE({}, {})",
+                self.render_expression(casted, params),
+                self.render_expression(default, params)
+            ),
+            Expression::Equals { left, right } => self.render_binary(left, "=", right, params),
+            Expression::LessThan { left, right } => self.render_binary(left, "<", right, params),
+            Expression::GreaterThan { left, right } => self.render_binary(left, ">", right, params),
+            Expression::LessThanOrEqual { left, right } => {
+                self.render_binary(left, "<=", right, params)
+            }
+            Expression::GreaterThanOrEqual { left, right } => {
+                self.render_binary(left, ">=", right, params)
+            }
+            Expression::Plus { left, right } => self.render_binary(left, "+", right, params),
+            Expression::Subtract { left, right } => self.render_binary(left, "-", right, params),
+            Expression::Divide { left, right } => self.render_binary(left, "/", right, params),
+            Expression::Multiply { left, right } => self.render_binary(left, "*", right, params),
+            Expression::Modulo { left, right } => self.render_binary(left, "%", right, params),
+            Expression::And { left, right } => self.render_binary(left, "AND", right, params),
+            Expression::Or { left, right } => self.render_binary(left, "OR", right, params),
+            Expression::BitAnd { left, right } => self.render_binary(left, "&", right, params),
+            Expression::BitOr { left, right } => self.render_binary(left, "|", right, params),
+            Expression::BitXOr { left, right } => {
+                self.render_binary(left, self.bitxor_op(), right, params)
+            }
+            Expression::Contains { left, right } => self.render_like(left, right, "%{}%", params),
+            Expression::StartsWith { left, right } => self.render_like(left, right, "{}%", params),
+            Expression::EndsWith { left, right } => self.render_like(left, right, "%{}", params),
+            Expression::Conditional {
+                condition,
+                true_path,
+                false_path,
+            } => format!(
+                "(CASE WHEN {} THEN {} ELSE {} END)",
+                self.render_expression(condition, params),
+                self.render_expression(true_path, params),
+                self.render_expression(false_path, params)
+            ),
+        }
+    }
+
+    fn render_binary(
+        &self,
+        left: &Expression,
+        op: &str,
+        right: &Expression,
+        params: &mut Vec<Constant>,
+    ) -> String {
+        format!(
+            "({} {} {})",
+            self.render_expression(left, params),
+            op,
+            self.render_expression(right, params)
+        )
+    }
+
+    /// Renders `left LIKE <pattern>`, escaping `%`, `_` and the escape
+    /// character itself when the pattern (`right`) is a literal [Constant::String].
+    /// When it isn't, the pattern is built dynamically at query time via
+    /// [Dialect::concat] and cannot be escaped, since its contents aren't
+    /// known here.
+    fn render_like(
+        &self,
+        left: &Expression,
+        right: &Expression,
+        template: &str,
+        params: &mut Vec<Constant>,
+    ) -> String {
+        let left_sql = self.render_expression(left, params);
+        let pattern = match right {
+            Expression::Constant(Constant::String(s)) => {
+                let escaped = escape_like_pattern(s);
+                let literal = template.replace("{}", &escaped);
+                params.push(Constant::String(literal));
+                self.placeholder(params.len())
+            }
+            _ => {
+                let right_sql = self.render_expression(right, params);
+                let (prefix, suffix) = template
+                    .split_once("{}")
+                    .expect("LIKE template always contains exactly one `{}`");
+                let mut parts = Vec::new();
+                if !prefix.is_empty() {
+                    params.push(Constant::String(prefix.to_string()));
+                    parts.push(self.placeholder(params.len()));
+                }
+                parts.push(right_sql);
+                if !suffix.is_empty() {
+                    params.push(Constant::String(suffix.to_string()));
+                    parts.push(self.placeholder(params.len()));
+                }
+                self.concat(&parts)
+            }
+        };
+        format!("({} LIKE {} ESCAPE '\\')", left_sql, pattern)
+    }
+}
+
+/// Escapes `%`, `_` and `\` in `pattern` with a `\` so it can be used
+/// literally in a `LIKE ... ESCAPE '\'` clause.
+fn escape_like_pattern(pattern: &str) -> String {
+    let mut escaped = String::with_capacity(pattern.len());
+    for c in pattern.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Converts a `serde_json` value from `Insert`/`Update` `data` into a
+/// [Constant] bind parameter. Returns `None` for JSON `null`, which is
+/// rendered as a literal `NULL` rather than bound.
+fn json_value_to_constant(value: &Value) -> Option<Constant> {
+    match value {
+        Value::Null => None,
+        Value::Bool(b) => Some(Constant::Bool(*b)),
+        Value::Number(n) => {
+            if let Some(i) = n.as_i64() {
+                Some(Constant::Int(i as usize))
+            } else if let Some(i) = n.as_u64() {
+                Some(Constant::Int(i as usize))
+            } else {
+                n.as_f64().map(Constant::Double)
+            }
+        }
+        Value::String(s) => Some(Constant::String(s.clone())),
+        Value::Array(_) | Value::Object(_) => None,
+    }
+}
+
+fn clone_constant(constant: &Constant) -> Constant {
+    match constant {
+        Constant::Bool(b) => Constant::Bool(*b),
+        Constant::Int(i) => Constant::Int(*i),
+        Constant::Double(d) => Constant::Double(*d),
+        Constant::String(s) => Constant::String(s.clone()),
+    }
+}
+
+/// The narrowest signed integer bounds that fit in a 16-bit column.
+const SMALLINT_RANGE: (isize, isize) = (i16::MIN as isize, i16::MAX as isize);
+/// The narrowest signed integer bounds that fit in a 32-bit column.
+const INT_RANGE: (isize, isize) = (i32::MIN as isize, i32::MAX as isize);
+
+fn narrowest_int_ddl(upper: isize, lower: isize, smallint: &str, int: &str, bigint: &str) -> String {
+    if lower >= SMALLINT_RANGE.0 && upper <= SMALLINT_RANGE.1 {
+        smallint.to_string()
+    } else if lower >= INT_RANGE.0 && upper <= INT_RANGE.1 {
+        int.to_string()
+    } else {
+        bigint.to_string()
+    }
+}
+
+/// The PostgreSQL dialect.
+pub struct Postgres;
+
+impl Dialect for Postgres {
+    fn quote_ident(&self, ident: &str) -> String {
+        format!("\"{}\"", ident)
+    }
+
+    fn placeholder(&self, index: usize) -> String {
+        format!("${}", index)
+    }
+
+    fn data_type_ddl(&self, data_type: &DataType) -> String {
+        match data_type {
+            DataType::Bool => "BOOLEAN".to_string(),
+            DataType::Int { upper, lower } => {
+                narrowest_int_ddl(*upper, *lower, "SMALLINT", "INTEGER", "BIGINT")
+            }
+            DataType::Double { .. } => "DOUBLE PRECISION".to_string(),
+            DataType::String { max_chars, .. } => format!("VARCHAR({})", max_chars),
+        }
+    }
+
+    fn concat(&self, parts: &[String]) -> String {
+        format!("({})", parts.join(" || "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ColumnReference, TableReference};
+
+    fn column_ref(table: &str, column: &str) -> Expression {
+        Expression::ColumnReference(ColumnReference {
+            table: TableReference { table: table.to_string() },
+            column: column.to_string(),
+        })
+    }
+
+    #[test]
+    fn contains_with_dynamic_right_keeps_both_percent_signs() {
+        let mut params = Vec::new();
+        let sql = Postgres.render_expression(
+            &Expression::Contains {
+                left: Box::new(column_ref("t", "name")),
+                right: Box::new(column_ref("t", "needle")),
+            },
+            &mut params,
+        );
+        // Both the literal "%" prefix and suffix must survive around the
+        // dynamic needle, not just one of them.
+        assert_eq!(
+            sql,
+            "(\"t\".\"name\" LIKE (($1 || \"t\".\"needle\") || $2) ESCAPE '\\')"
+        );
+        assert!(matches!(params.as_slice(), [Constant::String(a), Constant::String(b)] if a == "%" && b == "%"));
+    }
+
+    #[test]
+    fn starts_with_dynamic_right_has_only_a_suffix_percent() {
+        let mut params = Vec::new();
+        let sql = Postgres.render_expression(
+            &Expression::StartsWith {
+                left: Box::new(column_ref("t", "name")),
+                right: Box::new(column_ref("t", "prefix")),
+            },
+            &mut params,
+        );
+        assert_eq!(sql, "(\"t\".\"name\" LIKE (\"t\".\"prefix\" || $1) ESCAPE '\\')");
+        assert!(matches!(params.as_slice(), [Constant::String(s)] if s == "%"));
+    }
+
+    #[test]
+    fn json_value_to_constant_keeps_negative_integers_as_int() {
+        let value = serde_json::json!(-5);
+        assert!(matches!(json_value_to_constant(&value), Some(Constant::Int(i)) if i == -5i64 as usize));
+    }
+
+    #[test]
+    fn json_value_to_constant_keeps_large_positive_integers_as_int() {
+        let value = serde_json::json!(u64::MAX);
+        assert!(matches!(json_value_to_constant(&value), Some(Constant::Int(i)) if i == u64::MAX as usize));
+    }
+
+    #[test]
+    fn narrowest_int_ddl_picks_smallest_fitting_width() {
+        assert_eq!(narrowest_int_ddl(100, -100, "SMALLINT", "INT", "BIGINT"), "SMALLINT");
+        assert_eq!(narrowest_int_ddl(100_000, -100_000, "SMALLINT", "INT", "BIGINT"), "INT");
+        assert_eq!(
+            narrowest_int_ddl(isize::MAX, isize::MIN, "SMALLINT", "INT", "BIGINT"),
+            "BIGINT"
+        );
+    }
+}
+
+/// The MySQL dialect.
+pub struct MySql;
+
+impl Dialect for MySql {
+    fn quote_ident(&self, ident: &str) -> String {
+        format!("`{}`", ident)
+    }
+
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn data_type_ddl(&self, data_type: &DataType) -> String {
+        match data_type {
+            DataType::Bool => "TINYINT(1)".to_string(),
+            DataType::Int { upper, lower } => {
+                narrowest_int_ddl(*upper, *lower, "SMALLINT", "INT", "BIGINT")
+            }
+            DataType::Double { .. } => "DOUBLE".to_string(),
+            DataType::String { max_chars, .. } => format!("VARCHAR({})", max_chars),
+        }
+    }
+
+    fn concat(&self, parts: &[String]) -> String {
+        format!("CONCAT({})", parts.join(", "))
+    }
+
+    fn bitxor_op(&self) -> &'static str {
+        "^"
+    }
+}
+
+/// The SQLite dialect.
+pub struct Sqlite;
+
+impl Dialect for Sqlite {
+    fn quote_ident(&self, ident: &str) -> String {
+        format!("\"{}\"", ident)
+    }
+
+    fn placeholder(&self, _index: usize) -> String {
+        "?".to_string()
+    }
+
+    fn data_type_ddl(&self, data_type: &DataType) -> String {
+        match data_type {
+            DataType::Bool => "BOOLEAN".to_string(),
+            DataType::Int { upper, lower } => {
+                narrowest_int_ddl(*upper, *lower, "SMALLINT", "INTEGER", "BIGINT")
+            }
+            DataType::Double { .. } => "REAL".to_string(),
+            DataType::String { max_chars, .. } => format!("VARCHAR({})", max_chars),
+        }
+    }
+
+    fn concat(&self, parts: &[String]) -> String {
+        format!("({})", parts.join(" || "))
+    }
+}
@@ -0,0 +1,755 @@
+//! Version 1 of the exchange format.
+//!
+//! Every type in this module is a plain serde mirror of the wire format: it
+//! owns all `#[serde(...)]` attributes and never changes meaning once
+//! published, even as the canonical in-memory types at the crate root
+//! evolve. Introducing a `v2` is a matter of duplicating this module and
+//! writing the matching `From` impls; the rest of the crate only ever sees
+//! the canonical types and never branches on version.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The root structure, containing the rough layout of databases,
+/// as well as the transactions to run on them.
+///
+/// When the [Database] is incompatible with the database system or colums are mismatched,
+/// an implementation MUST disregard ALL transactions,
+/// not just the ones that reference incompatible databases/tables.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DefinitionFile {
+    /// The databases that are expected to exist for the transactions to function
+    pub databases: Vec<Database>,
+    /// The transactions to run, in order
+    pub transactions: Vec<Transaction>,
+}
+
+impl From<DefinitionFile> for crate::DefinitionFile {
+    fn from(file: DefinitionFile) -> Self {
+        crate::DefinitionFile {
+            databases: file.databases.into_iter().map(Into::into).collect(),
+            transactions: file.transactions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::DefinitionFile> for DefinitionFile {
+    fn from(file: crate::DefinitionFile) -> Self {
+        DefinitionFile {
+            databases: file.databases.into_iter().map(Into::into).collect(),
+            transactions: file.transactions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A database that is expected to exist and its tables.
+///
+/// Note that in [Transaction] the name is referenced via a [DatabaseReference]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Database {
+    /// The unique identifier of the database.
+    /// A database is later referenced by this name using a [DatabaseReference] in [Transaction].
+    /// A database name may only contain:
+    ///  - the letters a-z and A-Z
+    ///  - the numbers 0-9
+    ///  - the symbols '_', '-', '.'
+    pub name: String,
+    /// the tables that are expected to exist
+    pub tables: Vec<Table>,
+}
+
+impl From<Database> for crate::Database {
+    fn from(database: Database) -> Self {
+        crate::Database {
+            name: database.name,
+            tables: database.tables.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::Database> for Database {
+    fn from(database: crate::Database) -> Self {
+        Database {
+            name: database.name,
+            tables: database.tables.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A Table inside a database.
+/// Note that columns are referenced by their name later on, not their ordinal.
+///
+/// Can be referenced in an [Statement] using a [TableReference]
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Table {
+    /// the unique identifier of the table.
+    /// Unique in its Database (2 databases may have tables with equal names)
+    /// A table name may only contain:
+    //   - the letters a-z and A-Z
+    //   - the numbers 0-9
+    //   - the symbols '_', '-', '.'
+    pub name: String,
+    /// the columns in arbitrary order (see note)
+    pub columns: Vec<Column>,
+}
+
+impl From<Table> for crate::Table {
+    fn from(table: Table) -> Self {
+        crate::Table {
+            name: table.name,
+            columns: table.columns.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::Table> for Table {
+    fn from(table: crate::Table) -> Self {
+        Table {
+            name: table.name,
+            columns: table.columns.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// A column definition with its name and type.
+/// For conversions and notes on compatibility, see [Type].
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Column {
+    /// the unique identifier of the column.
+    /// You can have 2 Id columns as long as they are in different tables.
+    ///
+    /// A table name may only contain:
+    ///  - the letters a-z and A-Z
+    ///  - the numbers 0-9
+    ///  - the symbols '_', '-', '.'
+    pub name: String,
+    /// the type of the column
+    #[serde(rename = "type", flatten)]
+    pub _type: Type,
+}
+
+impl From<Column> for crate::Column {
+    fn from(column: Column) -> Self {
+        crate::Column {
+            name: column.name,
+            _type: column._type.into(),
+        }
+    }
+}
+
+impl From<crate::Column> for Column {
+    fn from(column: crate::Column) -> Self {
+        Column {
+            name: column.name,
+            _type: column._type.into(),
+        }
+    }
+}
+
+/// The Type of Column and requirements that are needed to satisfy basic requirements.
+///
+/// Care should be taken to verify that [Expression]s only assume valid conversions,
+/// but since the rules for converting and even the presence of a given type is dependent
+/// on the actual database used, they cannot be set in stone here.
+///
+/// Compatibility to your database's built in types MUST be checked, before attempting to execute [Transaction]s.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Type {
+    /// the actual data type
+    pub data_type: DataType,
+    /// if values of a given column must be unique
+    pub unique: bool,
+    /// if values may be null
+    pub not_null: bool,
+}
+
+impl From<Type> for crate::Type {
+    fn from(ty: Type) -> Self {
+        crate::Type {
+            data_type: ty.data_type.into(),
+            unique: ty.unique,
+            not_null: ty.not_null,
+        }
+    }
+}
+
+impl From<crate::Type> for Type {
+    fn from(ty: crate::Type) -> Self {
+        Type {
+            data_type: ty.data_type.into(),
+            unique: ty.unique,
+            not_null: ty.not_null,
+        }
+    }
+}
+
+/// the actual inner type of column
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "type", content = "bounds")]
+pub enum DataType {
+    /// A boolean, with values true and false.
+    ///
+    /// Do note, that ONLY true and false are specified,
+    /// conversions to/from integers are optional and implementation defined!
+    Bool,
+    /// any length of whole number
+    Int { upper: isize, lower: isize },
+    /// any length of decimal number
+    Double { upper: f64, lower: f64 },
+    /// A string of characters.
+    ///
+    /// Do note that while everything in this format is encoded in UTF-8 No BOM,
+    /// you may need to convert a string your native encoding.
+    /// Care should be taken to properly escape Strings as they run the risk of SQL injection.
+    String { min_chars: usize, max_chars: usize },
+}
+
+impl From<DataType> for crate::DataType {
+    fn from(data_type: DataType) -> Self {
+        match data_type {
+            DataType::Bool => crate::DataType::Bool,
+            DataType::Int { upper, lower } => crate::DataType::Int { upper, lower },
+            DataType::Double { upper, lower } => crate::DataType::Double { upper, lower },
+            DataType::String { min_chars, max_chars } => {
+                crate::DataType::String { min_chars, max_chars }
+            }
+        }
+    }
+}
+
+impl From<crate::DataType> for DataType {
+    fn from(data_type: crate::DataType) -> Self {
+        match data_type {
+            crate::DataType::Bool => DataType::Bool,
+            crate::DataType::Int { upper, lower } => DataType::Int { upper, lower },
+            crate::DataType::Double { upper, lower } => DataType::Double { upper, lower },
+            crate::DataType::String { min_chars, max_chars } => {
+                DataType::String { min_chars, max_chars }
+            }
+        }
+    }
+}
+
+/// A list of [Statements] to run on a single given database.
+///
+/// While it is implementation defined, if a Transaction is run inside of an actual
+/// transaction on the database, it is HIGHLY encouraged and
+/// not doing so should at the very least be logged.
+/// It is REQUIRED to skip statements that would occur after one that failed (and roll back the transaction, if supported).
+/// As an example with 4 statements, if statement 1 executes fine, but 2 throws an error, 3 and 4 MUST be skipped.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Transaction {
+    /// the database to run statements on.
+    pub database: DatabaseReference,
+    /// the statements to run in order.
+    pub statements: Vec<Statement>,
+}
+
+impl From<Transaction> for crate::Transaction {
+    fn from(transaction: Transaction) -> Self {
+        crate::Transaction {
+            database: transaction.database.into(),
+            statements: transaction.statements.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<crate::Transaction> for Transaction {
+    fn from(transaction: crate::Transaction) -> Self {
+        Transaction {
+            database: transaction.database.into(),
+            statements: transaction.statements.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DatabaseReference {
+    pub database: String,
+}
+
+impl From<DatabaseReference> for crate::DatabaseReference {
+    fn from(reference: DatabaseReference) -> Self {
+        crate::DatabaseReference {
+            database: reference.database,
+        }
+    }
+}
+
+impl From<crate::DatabaseReference> for DatabaseReference {
+    fn from(reference: crate::DatabaseReference) -> Self {
+        DatabaseReference {
+            database: reference.database,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Statement {
+    NoOp(NoOp),
+    Select(Select),
+    Delete(Delete),
+    Insert(Insert),
+    Update(Update),
+}
+
+impl From<Statement> for crate::Statement {
+    fn from(statement: Statement) -> Self {
+        match statement {
+            Statement::NoOp(s) => crate::Statement::NoOp(s.into()),
+            Statement::Select(s) => crate::Statement::Select(s.into()),
+            Statement::Delete(s) => crate::Statement::Delete(s.into()),
+            Statement::Insert(s) => crate::Statement::Insert(s.into()),
+            Statement::Update(s) => crate::Statement::Update(s.into()),
+        }
+    }
+}
+
+impl From<crate::Statement> for Statement {
+    fn from(statement: crate::Statement) -> Self {
+        match statement {
+            crate::Statement::NoOp(s) => Statement::NoOp(s.into()),
+            crate::Statement::Select(s) => Statement::Select(s.into()),
+            crate::Statement::Delete(s) => Statement::Delete(s.into()),
+            crate::Statement::Insert(s) => Statement::Insert(s.into()),
+            crate::Statement::Update(s) => Statement::Update(s.into()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NoOp {}
+
+impl From<NoOp> for crate::NoOp {
+    fn from(_: NoOp) -> Self {
+        crate::NoOp {}
+    }
+}
+
+impl From<crate::NoOp> for NoOp {
+    fn from(_: crate::NoOp) -> Self {
+        NoOp {}
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Select {
+    pub table: TableReference,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_condition: Option<Expression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Expression>,
+}
+
+impl From<Select> for crate::Select {
+    fn from(select: Select) -> Self {
+        crate::Select {
+            table: select.table.into(),
+            execution_condition: select.execution_condition.map(Into::into),
+            filter: select.filter.map(Into::into),
+        }
+    }
+}
+
+impl From<crate::Select> for Select {
+    fn from(select: crate::Select) -> Self {
+        Select {
+            table: select.table.into(),
+            execution_condition: select.execution_condition.map(Into::into),
+            filter: select.filter.map(Into::into),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Delete {
+    pub table: TableReference,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_condition: Option<Expression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Expression>,
+}
+
+impl From<Delete> for crate::Delete {
+    fn from(delete: Delete) -> Self {
+        crate::Delete {
+            table: delete.table.into(),
+            execution_condition: delete.execution_condition.map(Into::into),
+            filter: delete.filter.map(Into::into),
+        }
+    }
+}
+
+impl From<crate::Delete> for Delete {
+    fn from(delete: crate::Delete) -> Self {
+        Delete {
+            table: delete.table.into(),
+            execution_condition: delete.execution_condition.map(Into::into),
+            filter: delete.filter.map(Into::into),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Insert {
+    pub table: TableReference,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_condition: Option<Expression>,
+    pub data: Vec<Map<String, Value>>,
+}
+
+impl From<Insert> for crate::Insert {
+    fn from(insert: Insert) -> Self {
+        crate::Insert {
+            table: insert.table.into(),
+            execution_condition: insert.execution_condition.map(Into::into),
+            data: insert.data,
+        }
+    }
+}
+
+impl From<crate::Insert> for Insert {
+    fn from(insert: crate::Insert) -> Self {
+        Insert {
+            table: insert.table.into(),
+            execution_condition: insert.execution_condition.map(Into::into),
+            data: insert.data,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Update {
+    pub table: TableReference,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub execution_condition: Option<Expression>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<Expression>,
+    pub data: Map<String, Value>,
+}
+
+impl From<Update> for crate::Update {
+    fn from(update: Update) -> Self {
+        crate::Update {
+            table: update.table.into(),
+            execution_condition: update.execution_condition.map(Into::into),
+            filter: update.filter.map(Into::into),
+            data: update.data,
+        }
+    }
+}
+
+impl From<crate::Update> for Update {
+    fn from(update: crate::Update) -> Self {
+        Update {
+            table: update.table.into(),
+            execution_condition: update.execution_condition.map(Into::into),
+            filter: update.filter.map(Into::into),
+            data: update.data,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct TableReference {
+    pub table: String,
+}
+
+impl From<TableReference> for crate::TableReference {
+    fn from(reference: TableReference) -> Self {
+        crate::TableReference {
+            table: reference.table,
+        }
+    }
+}
+
+impl From<crate::TableReference> for TableReference {
+    fn from(reference: crate::TableReference) -> Self {
+        TableReference {
+            table: reference.table,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Expression {
+    Constant(Constant),
+    ColumnReference(ColumnReference),
+    Not(Box<Expression>),
+    NotNull {
+        casted: Box<Expression>,
+        default: Box<Expression>,
+    },
+    Equals { left: Box<Expression>, right: Box<Expression> },
+    LessThan { left: Box<Expression>, right: Box<Expression> },
+    GreaterThan { left: Box<Expression>, right: Box<Expression> },
+    LessThanOrEqual { left: Box<Expression>, right: Box<Expression> },
+    GreaterThanOrEqual { left: Box<Expression>, right: Box<Expression> },
+    Plus { left: Box<Expression>, right: Box<Expression> },
+    Subtract { left: Box<Expression>, right: Box<Expression> },
+    Divide { left: Box<Expression>, right: Box<Expression> },
+    Multiply { left: Box<Expression>, right: Box<Expression> },
+    Modulo { left: Box<Expression>, right: Box<Expression> },
+    And { left: Box<Expression>, right: Box<Expression> },
+    Or { left: Box<Expression>, right: Box<Expression> },
+    BitAnd { left: Box<Expression>, right: Box<Expression> },
+    BitOr { left: Box<Expression>, right: Box<Expression> },
+    BitXOr { left: Box<Expression>, right: Box<Expression> },
+    Contains { left: Box<Expression>, right: Box<Expression> },
+    StartsWith { left: Box<Expression>, right: Box<Expression> },
+    EndsWith { left: Box<Expression>, right: Box<Expression> },
+    Conditional {
+        condition: Box<Expression>,
+        true_path: Box<Expression>,
+        false_path: Box<Expression>,
+    },
+}
+
+impl From<Expression> for crate::Expression {
+    fn from(expr: Expression) -> Self {
+        match expr {
+            Expression::Constant(c) => crate::Expression::Constant(c.into()),
+            Expression::ColumnReference(c) => crate::Expression::ColumnReference(c.into()),
+            Expression::Not(e) => crate::Expression::Not(Box::new((*e).into())),
+            Expression::NotNull { casted, default } => crate::Expression::NotNull {
+                casted: Box::new((*casted).into()),
+                default: Box::new((*default).into()),
+            },
+            Expression::Equals { left, right } => crate::Expression::Equals {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::LessThan { left, right } => crate::Expression::LessThan {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::GreaterThan { left, right } => crate::Expression::GreaterThan {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::LessThanOrEqual { left, right } => crate::Expression::LessThanOrEqual {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::GreaterThanOrEqual { left, right } => {
+                crate::Expression::GreaterThanOrEqual {
+                    left: Box::new((*left).into()),
+                    right: Box::new((*right).into()),
+                }
+            }
+            Expression::Plus { left, right } => crate::Expression::Plus {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::Subtract { left, right } => crate::Expression::Subtract {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::Divide { left, right } => crate::Expression::Divide {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::Multiply { left, right } => crate::Expression::Multiply {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::Modulo { left, right } => crate::Expression::Modulo {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::And { left, right } => crate::Expression::And {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::Or { left, right } => crate::Expression::Or {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::BitAnd { left, right } => crate::Expression::BitAnd {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::BitOr { left, right } => crate::Expression::BitOr {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::BitXOr { left, right } => crate::Expression::BitXOr {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::Contains { left, right } => crate::Expression::Contains {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::StartsWith { left, right } => crate::Expression::StartsWith {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::EndsWith { left, right } => crate::Expression::EndsWith {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            Expression::Conditional {
+                condition,
+                true_path,
+                false_path,
+            } => crate::Expression::Conditional {
+                condition: Box::new((*condition).into()),
+                true_path: Box::new((*true_path).into()),
+                false_path: Box::new((*false_path).into()),
+            },
+        }
+    }
+}
+
+impl From<crate::Expression> for Expression {
+    fn from(expr: crate::Expression) -> Self {
+        match expr {
+            crate::Expression::Constant(c) => Expression::Constant(c.into()),
+            crate::Expression::ColumnReference(c) => Expression::ColumnReference(c.into()),
+            crate::Expression::Not(e) => Expression::Not(Box::new((*e).into())),
+            crate::Expression::NotNull { casted, default } => Expression::NotNull {
+                casted: Box::new((*casted).into()),
+                default: Box::new((*default).into()),
+            },
+            crate::Expression::Equals { left, right } => Expression::Equals {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::LessThan { left, right } => Expression::LessThan {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::GreaterThan { left, right } => Expression::GreaterThan {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::LessThanOrEqual { left, right } => Expression::LessThanOrEqual {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::GreaterThanOrEqual { left, right } => {
+                Expression::GreaterThanOrEqual {
+                    left: Box::new((*left).into()),
+                    right: Box::new((*right).into()),
+                }
+            }
+            crate::Expression::Plus { left, right } => Expression::Plus {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::Subtract { left, right } => Expression::Subtract {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::Divide { left, right } => Expression::Divide {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::Multiply { left, right } => Expression::Multiply {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::Modulo { left, right } => Expression::Modulo {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::And { left, right } => Expression::And {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::Or { left, right } => Expression::Or {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::BitAnd { left, right } => Expression::BitAnd {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::BitOr { left, right } => Expression::BitOr {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::BitXOr { left, right } => Expression::BitXOr {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::Contains { left, right } => Expression::Contains {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::StartsWith { left, right } => Expression::StartsWith {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::EndsWith { left, right } => Expression::EndsWith {
+                left: Box::new((*left).into()),
+                right: Box::new((*right).into()),
+            },
+            crate::Expression::Conditional {
+                condition,
+                true_path,
+                false_path,
+            } => Expression::Conditional {
+                condition: Box::new((*condition).into()),
+                true_path: Box::new((*true_path).into()),
+                false_path: Box::new((*false_path).into()),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum Constant {
+    Bool(bool),
+    Int(usize),
+    Double(f64),
+    String(String),
+}
+
+impl From<Constant> for crate::Constant {
+    fn from(constant: Constant) -> Self {
+        match constant {
+            Constant::Bool(b) => crate::Constant::Bool(b),
+            Constant::Int(i) => crate::Constant::Int(i),
+            Constant::Double(d) => crate::Constant::Double(d),
+            Constant::String(s) => crate::Constant::String(s),
+        }
+    }
+}
+
+impl From<crate::Constant> for Constant {
+    fn from(constant: crate::Constant) -> Self {
+        match constant {
+            crate::Constant::Bool(b) => Constant::Bool(b),
+            crate::Constant::Int(i) => Constant::Int(i),
+            crate::Constant::Double(d) => Constant::Double(d),
+            crate::Constant::String(s) => Constant::String(s),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ColumnReference {
+    #[serde(flatten)]
+    pub table: TableReference,
+    pub column: String,
+}
+
+impl From<ColumnReference> for crate::ColumnReference {
+    fn from(reference: ColumnReference) -> Self {
+        crate::ColumnReference {
+            table: reference.table.into(),
+            column: reference.column,
+        }
+    }
+}
+
+impl From<crate::ColumnReference> for ColumnReference {
+    fn from(reference: crate::ColumnReference) -> Self {
+        ColumnReference {
+            table: reference.table.into(),
+            column: reference.column,
+        }
+    }
+}
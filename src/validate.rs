@@ -0,0 +1,481 @@
+//! Static type checking for [Expression]s.
+//!
+//! Walks a [DefinitionFile] and rejects malformed expressions (unknown
+//! tables/columns, operators applied to the wrong types, nullability
+//! mismatches) before any [crate::Transaction] is executed.
+
+use crate::{
+    Database, DataType, DefinitionFile, Expression, Statement, Table, Transaction,
+};
+
+/// Where, inside an [Expression] tree, a [ValidationError] occurred, e.g.
+/// `["left", "casted"]` for the `casted` operand of a `NotNull` nested under
+/// the left side of some comparison.
+pub type Path = Vec<&'static str>;
+
+/// A single problem found while type-checking a [DefinitionFile].
+#[derive(Debug)]
+pub struct ValidationError {
+    /// Path to the offending sub-expression, outermost segment first.
+    pub path: Path,
+    pub kind: ValidationErrorKind,
+}
+
+#[derive(Debug)]
+pub enum ValidationErrorKind {
+    /// A [crate::ColumnReference] named a table that doesn't exist in the schema.
+    UnknownTable { table: String },
+    /// A [crate::ColumnReference] named a column that doesn't exist on its table.
+    UnknownColumn { table: String, column: String },
+    /// An operator that requires `Bool` operands was given something else.
+    NotBool { found: &'static str },
+    /// An operator that requires numeric (`Int`/`Double`) operands was given something else.
+    NotNumeric { found: &'static str },
+    /// An operator that requires `Int` operands was given something else.
+    NotInt { found: &'static str },
+    /// An operator that requires `String` operands was given something else.
+    NotString { found: &'static str },
+    /// Two operands were required to share a type, but didn't.
+    TypeMismatch {
+        left: &'static str,
+        right: &'static str,
+    },
+    /// `NotNull`'s `default` must not itself be nullable.
+    DefaultIsNullable,
+}
+
+type Result<T> = std::result::Result<T, ValidationError>;
+
+impl ValidationError {
+    fn new(kind: ValidationErrorKind) -> Self {
+        ValidationError {
+            path: Vec::new(),
+            kind,
+        }
+    }
+
+    /// Prepends `segment` to the path, as an error propagates back up the
+    /// expression tree it was found in.
+    fn under(mut self, segment: &'static str) -> Self {
+        self.path.insert(0, segment);
+        self
+    }
+}
+
+/// The "kind" of a [DataType], ignoring its bounds, for comparing two types
+/// without caring about the exact range/length each declares.
+fn kind(data_type: &DataType) -> &'static str {
+    match data_type {
+        DataType::Bool => "bool",
+        DataType::Int { .. } => "int",
+        DataType::Double { .. } => "double",
+        DataType::String { .. } => "string",
+    }
+}
+
+fn is_numeric(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Int { .. } | DataType::Double { .. })
+}
+
+fn expect_bool(data_type: &DataType) -> Result<()> {
+    if matches!(data_type, DataType::Bool) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(ValidationErrorKind::NotBool {
+            found: kind(data_type),
+        }))
+    }
+}
+
+fn expect_numeric(data_type: &DataType) -> Result<()> {
+    if is_numeric(data_type) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(ValidationErrorKind::NotNumeric {
+            found: kind(data_type),
+        }))
+    }
+}
+
+fn expect_int(data_type: &DataType) -> Result<()> {
+    if matches!(data_type, DataType::Int { .. }) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(ValidationErrorKind::NotInt {
+            found: kind(data_type),
+        }))
+    }
+}
+
+fn expect_string(data_type: &DataType) -> Result<()> {
+    if matches!(data_type, DataType::String { .. }) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(ValidationErrorKind::NotString {
+            found: kind(data_type),
+        }))
+    }
+}
+
+/// Widens two `Int` bounds (or `Double` bounds, promoting either side that's
+/// an `Int`) to a single type that covers both operands.
+fn unify_numeric(left: &DataType, right: &DataType) -> DataType {
+    match (left, right) {
+        (DataType::Int { upper: lu, lower: ll }, DataType::Int { upper: ru, lower: rl }) => {
+            DataType::Int {
+                upper: (*lu).max(*ru),
+                lower: (*ll).min(*rl),
+            }
+        }
+        _ => {
+            let as_f64 = |d: &DataType| -> (f64, f64) {
+                match d {
+                    DataType::Int { upper, lower } => (*lower as f64, *upper as f64),
+                    DataType::Double { upper, lower } => (*lower, *upper),
+                    _ => unreachable!("unify_numeric is only called with numeric operands"),
+                }
+            };
+            let (ll, lu) = as_f64(left);
+            let (rl, ru) = as_f64(right);
+            DataType::Double {
+                upper: lu.max(ru),
+                lower: ll.min(rl),
+            }
+        }
+    }
+}
+
+/// Widens two `String` bounds to a single type that covers both operands:
+/// the narrower `min_chars` and the wider `max_chars`.
+fn unify_string(left: &DataType, right: &DataType) -> DataType {
+    match (left, right) {
+        (
+            DataType::String { min_chars: lmin, max_chars: lmax },
+            DataType::String { min_chars: rmin, max_chars: rmax },
+        ) => DataType::String {
+            min_chars: (*lmin).min(*rmin),
+            max_chars: (*lmax).max(*rmax),
+        },
+        _ => unreachable!("unify_string is only called with String operands"),
+    }
+}
+
+/// Finds the [Table] named `name`, first in `table` itself (the common case
+/// of an expression referencing its own statement's table), then across
+/// every [Database] in `schema`.
+fn resolve_table<'s>(name: &str, table: &'s Table, schema: &'s DefinitionFile) -> Option<&'s Table> {
+    if table.name == name {
+        return Some(table);
+    }
+    schema
+        .databases
+        .iter()
+        .flat_map(|d: &Database| d.tables.iter())
+        .find(|t| t.name == name)
+}
+
+/// Infers the type of `expr` as evaluated against `table`, resolving any
+/// [crate::ColumnReference] via `table` or, failing that, any table in
+/// `schema`. Returns the inferred [DataType] and whether the result may be
+/// `NULL`.
+pub fn infer_type(expr: &Expression, table: &Table, schema: &DefinitionFile) -> Result<(DataType, bool)> {
+    match expr {
+        Expression::Constant(c) => Ok((constant_type(c), false)),
+        Expression::ColumnReference(c) => {
+            let target = resolve_table(&c.table.table, table, schema).ok_or_else(|| {
+                ValidationError::new(ValidationErrorKind::UnknownTable {
+                    table: c.table.table.clone(),
+                })
+            })?;
+            let column = target
+                .columns
+                .iter()
+                .find(|col| col.name == c.column)
+                .ok_or_else(|| {
+                    ValidationError::new(ValidationErrorKind::UnknownColumn {
+                        table: target.name.clone(),
+                        column: c.column.clone(),
+                    })
+                })?;
+            Ok((clone_data_type(&column._type.data_type), !column._type.not_null))
+        }
+        Expression::Not(e) => {
+            let (ty, nullable) = infer_type(e, table, schema).map_err(|e| e.under("operand"))?;
+            expect_bool(&ty).map_err(|e| e.under("operand"))?;
+            Ok((DataType::Bool, nullable))
+        }
+        Expression::NotNull { casted, default } => {
+            let (casted_ty, _) = infer_type(casted, table, schema).map_err(|e| e.under("casted"))?;
+            let (default_ty, default_nullable) =
+                infer_type(default, table, schema).map_err(|e| e.under("default"))?;
+            if default_nullable {
+                return Err(ValidationError::new(ValidationErrorKind::DefaultIsNullable));
+            }
+            if kind(&casted_ty) != kind(&default_ty) {
+                return Err(ValidationError::new(ValidationErrorKind::TypeMismatch {
+                    left: kind(&casted_ty),
+                    right: kind(&default_ty),
+                }));
+            }
+            Ok((casted_ty, false))
+        }
+        Expression::Equals { left, right }
+        | Expression::LessThan { left, right }
+        | Expression::GreaterThan { left, right }
+        | Expression::LessThanOrEqual { left, right }
+        | Expression::GreaterThanOrEqual { left, right } => {
+            let (left_ty, left_null) = infer_type(left, table, schema).map_err(|e| e.under("left"))?;
+            let (right_ty, right_null) = infer_type(right, table, schema).map_err(|e| e.under("right"))?;
+            let comparable = (is_numeric(&left_ty) && is_numeric(&right_ty)) || kind(&left_ty) == kind(&right_ty);
+            if !comparable {
+                return Err(ValidationError::new(ValidationErrorKind::TypeMismatch {
+                    left: kind(&left_ty),
+                    right: kind(&right_ty),
+                }));
+            }
+            Ok((DataType::Bool, left_null || right_null))
+        }
+        Expression::Plus { left, right }
+        | Expression::Subtract { left, right }
+        | Expression::Divide { left, right }
+        | Expression::Multiply { left, right } => {
+            let (left_ty, left_null) = infer_type(left, table, schema).map_err(|e| e.under("left"))?;
+            let (right_ty, right_null) = infer_type(right, table, schema).map_err(|e| e.under("right"))?;
+            expect_numeric(&left_ty).map_err(|e| e.under("left"))?;
+            expect_numeric(&right_ty).map_err(|e| e.under("right"))?;
+            Ok((unify_numeric(&left_ty, &right_ty), left_null || right_null))
+        }
+        Expression::And { left, right } | Expression::Or { left, right } => {
+            let (left_ty, left_null) = infer_type(left, table, schema).map_err(|e| e.under("left"))?;
+            let (right_ty, right_null) = infer_type(right, table, schema).map_err(|e| e.under("right"))?;
+            expect_bool(&left_ty).map_err(|e| e.under("left"))?;
+            expect_bool(&right_ty).map_err(|e| e.under("right"))?;
+            Ok((DataType::Bool, left_null || right_null))
+        }
+        Expression::BitAnd { left, right }
+        | Expression::BitOr { left, right }
+        | Expression::BitXOr { left, right }
+        | Expression::Modulo { left, right } => {
+            let (left_ty, left_null) = infer_type(left, table, schema).map_err(|e| e.under("left"))?;
+            let (right_ty, right_null) = infer_type(right, table, schema).map_err(|e| e.under("right"))?;
+            expect_int(&left_ty).map_err(|e| e.under("left"))?;
+            expect_int(&right_ty).map_err(|e| e.under("right"))?;
+            Ok((unify_numeric(&left_ty, &right_ty), left_null || right_null))
+        }
+        Expression::Contains { left, right }
+        | Expression::StartsWith { left, right }
+        | Expression::EndsWith { left, right } => {
+            let (left_ty, left_null) = infer_type(left, table, schema).map_err(|e| e.under("left"))?;
+            let (right_ty, right_null) = infer_type(right, table, schema).map_err(|e| e.under("right"))?;
+            expect_string(&left_ty).map_err(|e| e.under("left"))?;
+            expect_string(&right_ty).map_err(|e| e.under("right"))?;
+            Ok((DataType::Bool, left_null || right_null))
+        }
+        Expression::Conditional {
+            condition,
+            true_path,
+            false_path,
+        } => {
+            let (condition_ty, condition_null) =
+                infer_type(condition, table, schema).map_err(|e| e.under("condition"))?;
+            expect_bool(&condition_ty).map_err(|e| e.under("condition"))?;
+            let (true_ty, true_null) = infer_type(true_path, table, schema).map_err(|e| e.under("true_path"))?;
+            let (false_ty, false_null) =
+                infer_type(false_path, table, schema).map_err(|e| e.under("false_path"))?;
+            let unified = if kind(&true_ty) == kind(&false_ty) {
+                if is_numeric(&true_ty) {
+                    unify_numeric(&true_ty, &false_ty)
+                } else if matches!(true_ty, DataType::String { .. }) {
+                    unify_string(&true_ty, &false_ty)
+                } else {
+                    true_ty
+                }
+            } else if is_numeric(&true_ty) && is_numeric(&false_ty) {
+                unify_numeric(&true_ty, &false_ty)
+            } else {
+                return Err(ValidationError::new(ValidationErrorKind::TypeMismatch {
+                    left: kind(&true_ty),
+                    right: kind(&false_ty),
+                }));
+            };
+            Ok((unified, condition_null || true_null || false_null))
+        }
+    }
+}
+
+fn constant_type(constant: &crate::Constant) -> DataType {
+    match constant {
+        crate::Constant::Bool(_) => DataType::Bool,
+        crate::Constant::Int(i) => DataType::Int {
+            upper: *i as isize,
+            lower: *i as isize,
+        },
+        crate::Constant::Double(d) => DataType::Double { upper: *d, lower: *d },
+        crate::Constant::String(s) => DataType::String {
+            min_chars: s.chars().count(),
+            max_chars: s.chars().count(),
+        },
+    }
+}
+
+fn clone_data_type(data_type: &DataType) -> DataType {
+    match data_type {
+        DataType::Bool => DataType::Bool,
+        DataType::Int { upper, lower } => DataType::Int {
+            upper: *upper,
+            lower: *lower,
+        },
+        DataType::Double { upper, lower } => DataType::Double {
+            upper: *upper,
+            lower: *lower,
+        },
+        DataType::String { min_chars, max_chars } => DataType::String {
+            min_chars: *min_chars,
+            max_chars: *max_chars,
+        },
+    }
+}
+
+/// A [ValidationError] together with where in the [DefinitionFile] it was found.
+#[derive(Debug)]
+pub struct ValidationIssue {
+    pub transaction: usize,
+    pub statement: usize,
+    pub field: &'static str,
+    pub error: ValidationError,
+}
+
+/// Walks every [Transaction] in `file`, type-checking each statement's
+/// `execution_condition` and `filter` expressions against its target
+/// table, and collects every problem found rather than stopping at the first.
+pub fn validate(file: &DefinitionFile) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+
+    for (transaction_idx, transaction) in file.transactions.iter().enumerate() {
+        let database = match find_database(file, &transaction.database.database) {
+            Some(database) => database,
+            None => continue,
+        };
+
+        for (statement_idx, statement) in transaction.statements.iter().enumerate() {
+            check_statement(
+                file,
+                database,
+                transaction,
+                transaction_idx,
+                statement_idx,
+                statement,
+                &mut issues,
+            );
+        }
+    }
+
+    issues
+}
+
+fn find_database<'s>(file: &'s DefinitionFile, name: &str) -> Option<&'s Database> {
+    file.databases.iter().find(|d| d.name == name)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn check_statement(
+    file: &DefinitionFile,
+    database: &Database,
+    _transaction: &Transaction,
+    transaction_idx: usize,
+    statement_idx: usize,
+    statement: &Statement,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let (table_ref, execution_condition, filter): (_, _, Option<&Expression>) = match statement {
+        Statement::NoOp(_) => return,
+        Statement::Select(s) => (&s.table, &s.execution_condition, s.filter.as_ref()),
+        Statement::Delete(d) => (&d.table, &d.execution_condition, d.filter.as_ref()),
+        Statement::Insert(i) => (&i.table, &i.execution_condition, None),
+        Statement::Update(u) => (&u.table, &u.execution_condition, u.filter.as_ref()),
+    };
+
+    let Some(table) = database.tables.iter().find(|t| t.name == table_ref.table) else {
+        return;
+    };
+
+    let checks: [(&'static str, Option<&Expression>); 2] =
+        [("execution_condition", execution_condition.as_ref()), ("filter", filter)];
+    for (field, expr) in checks {
+        let Some(expr) = expr else { continue };
+        if let Err(error) = infer_type(expr, table, file) {
+            issues.push(ValidationIssue {
+                transaction: transaction_idx,
+                statement: statement_idx,
+                field,
+                error,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Column, Type};
+
+    fn string_column(name: &str, min_chars: usize, max_chars: usize) -> Table {
+        Table {
+            name: "t".to_string(),
+            columns: vec![Column {
+                name: name.to_string(),
+                _type: Type {
+                    data_type: DataType::String { min_chars, max_chars },
+                    unique: false,
+                    not_null: true,
+                },
+            }],
+        }
+    }
+
+    fn empty_schema() -> DefinitionFile {
+        DefinitionFile { databases: Vec::new(), transactions: Vec::new() }
+    }
+
+    fn string_const(s: &str) -> Expression {
+        Expression::Constant(crate::Constant::String(s.to_string()))
+    }
+
+    #[test]
+    fn conditional_unifies_string_bounds_from_both_branches() {
+        let table = string_column("name", 1, 1);
+        let schema = empty_schema();
+        let expr = Expression::Conditional {
+            condition: Box::new(Expression::Constant(crate::Constant::Bool(true))),
+            true_path: Box::new(string_const("a")),
+            false_path: Box::new(string_const("abcd")),
+        };
+        let (ty, _) = infer_type(&expr, &table, &schema).expect("both branches are strings");
+        match ty {
+            DataType::String { min_chars, max_chars } => {
+                assert_eq!(min_chars, 1);
+                assert_eq!(max_chars, 4);
+            }
+            other => panic!("expected String, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn conditional_unifies_numeric_branches_as_before() {
+        let table = string_column("name", 1, 1);
+        let schema = empty_schema();
+        let expr = Expression::Conditional {
+            condition: Box::new(Expression::Constant(crate::Constant::Bool(true))),
+            true_path: Box::new(Expression::Constant(crate::Constant::Int(1))),
+            false_path: Box::new(Expression::Constant(crate::Constant::Int(100))),
+        };
+        let (ty, _) = infer_type(&expr, &table, &schema).expect("both branches are ints");
+        match ty {
+            DataType::Int { upper, lower } => {
+                assert_eq!(lower, 1);
+                assert_eq!(upper, 100);
+            }
+            other => panic!("expected Int, got {:?}", other),
+        }
+    }
+}